@@ -0,0 +1,188 @@
+//! Async adapters for [`Producer`](crate::Producer) and [`Consumer`](crate::Consumer).
+//!
+//! The synchronous [`push()`](crate::Producer::push) and
+//! [`pop()`](crate::Consumer::pop) methods return immediately with an error
+//! when the queue is full/empty, which is what makes `rtrb` usable from a
+//! real-time thread. The non-real-time side of a pipeline often prefers to
+//! `.await` instead of spinning, which is what this module provides.
+//!
+//! Besides the [`Future`]-based [`Producer::push_async()`]/
+//! [`Consumer::pop_async()`], [`Consumer<T>`] implements
+//! [`futures_core::Stream`] and [`Producer<T>`] implements
+//! [`futures_sink::Sink`], for use with `futures` combinators.
+//!
+//! Enabled by the `async_` feature.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use crate::{Consumer, Producer, PushError};
+
+impl<T> Producer<T> {
+    /// Returns a [`Future`] that resolves once `value` has been pushed into
+    /// the queue.
+    ///
+    /// If the queue is currently full, the [`Consumer`] end wakes this
+    /// future again as soon as a slot becomes available.
+    pub fn push_async(&mut self, value: T) -> PushFuture<'_, T> {
+        PushFuture {
+            producer: self,
+            value: Some(value),
+        }
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Returns a [`Future`] that resolves with the next element once one
+    /// becomes available.
+    ///
+    /// If the queue is currently empty, the [`Producer`] end wakes this
+    /// future again as soon as a new element has been pushed.
+    pub fn pop_async(&mut self) -> PopFuture<'_, T> {
+        PopFuture { consumer: self }
+    }
+}
+
+/// A [`Future`] that resolves once a value has been pushed into the queue.
+///
+/// Created by [`Producer::push_async()`].
+#[derive(Debug)]
+pub struct PushFuture<'a, T> {
+    producer: &'a mut Producer<T>,
+    value: Option<T>,
+}
+
+// Neither field is self-referential, so polling never requires pinning `T` itself.
+impl<T> Unpin for PushFuture<'_, T> {}
+
+impl<T> Future for PushFuture<'_, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        let value = this.value.take().expect("polled PushFuture after completion");
+        // Register the waker *before* trying to push again, so that a
+        // notification sent by the consumer in between is never missed.
+        this.producer.rb.producer_waker.register(cx.waker());
+        match this.producer.push(value) {
+            Ok(()) => Poll::Ready(()),
+            Err(PushError::Full(value)) => {
+                this.value = Some(value);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A [`Future`] that resolves with the next element from the queue.
+///
+/// Created by [`Consumer::pop_async()`].
+#[derive(Debug)]
+pub struct PopFuture<'a, T> {
+    consumer: &'a mut Consumer<T>,
+}
+
+impl<T> Unpin for PopFuture<'_, T> {}
+
+impl<T> Future for PopFuture<'_, T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let this = self.get_mut();
+        // Register the waker *before* trying to pop again, so that a push
+        // sent by the producer in between is never missed.
+        this.consumer.rb.consumer_waker.register(cx.waker());
+        match this.consumer.pop() {
+            Ok(value) => Poll::Ready(value),
+            Err(_) => Poll::Pending,
+        }
+    }
+}
+
+impl futures_io::AsyncWrite for Producer<u8> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        this.rb.producer_waker.register(cx.waker());
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        match this.write_slice(buf) {
+            0 => Poll::Pending,
+            n => Poll::Ready(Ok(n)),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl futures_io::AsyncRead for Consumer<u8> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        this.rb.consumer_waker.register(cx.waker());
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        match this.read_slice(buf) {
+            0 => Poll::Pending,
+            n => Poll::Ready(Ok(n)),
+        }
+    }
+}
+
+impl<T> futures_core::Stream for Consumer<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let this = self.get_mut();
+        // Register the waker *before* trying to pop again, so that a push
+        // sent by the producer in between is never missed.
+        this.rb.consumer_waker.register(cx.waker());
+        match this.pop() {
+            Ok(value) => Poll::Ready(Some(value)),
+            // A dropped producer has no way to signal end-of-stream, so an
+            // empty queue is always `Pending` and the stream never yields `None`.
+            Err(_) => Poll::Pending,
+        }
+    }
+}
+
+impl<T> futures_sink::Sink<T> for Producer<T> {
+    type Error = PushError<T>;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        this.rb.producer_waker.register(cx.waker());
+        if this.is_full() {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+        self.get_mut().push(item)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,56 @@
+//! [`std::io::Read`]/[`std::io::Write`] impls for the byte-oriented case.
+
+use std::io::{Error, ErrorKind, Read, Result, Write};
+
+use crate::{Consumer, Producer};
+
+impl Write for Producer<u8> {
+    /// Writes as many bytes as currently fit into the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorKind::WouldBlock`] if the queue is currently full,
+    /// rather than `Ok(0)`. `std::io` convention reserves `Ok(0)` from
+    /// `write()` for "this sink will never accept more data" (a closed
+    /// pipe, say), which is exactly what [`write_all()`](Write::write_all)
+    /// interprets it as, turning it into a `WriteZero` error instead of
+    /// retrying. A full queue is a transient condition, not that, so it is
+    /// reported as the non-blocking-I/O convention for "try again later"
+    /// instead.
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        match self.write_slice(buf) {
+            0 => Err(Error::from(ErrorKind::WouldBlock)),
+            n => Ok(n),
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Read for Consumer<u8> {
+    /// Reads as many bytes as currently available from the queue.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ErrorKind::WouldBlock`] if the queue is currently empty,
+    /// rather than `Ok(0)`. `std::io` convention reserves `Ok(0)` from
+    /// `read()` for end-of-stream, which is exactly what
+    /// [`read_exact()`](Read::read_exact) interprets it as, turning it into
+    /// an `UnexpectedEof` error instead of retrying. An empty queue is a
+    /// transient condition, not end-of-stream, so it is reported as the
+    /// non-blocking-I/O convention for "try again later" instead.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        match self.read_slice(buf) {
+            0 => Err(Error::from(ErrorKind::WouldBlock)),
+            n => Ok(n),
+        }
+    }
+}
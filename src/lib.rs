@@ -8,6 +8,16 @@
 //! for new data, other than trying repeatedly until reading succeeds.
 //! Similarly, if the queue is full, there is no way for the writing thread
 //! to wait for newly available space to write to, other than trying repeatedly.
+//! With the `async_` feature enabled, the [`async_`] module provides
+//! `.await`-based alternatives for the non-real-time side of a pipeline.
+//! With the `std` feature enabled (on by default), [`Producer<u8>`] and
+//! [`Consumer<u8>`] implement [`std::io::Write`] and [`std::io::Read`].
+//! With the `no_std` feature enabled, the [`no_std`] module provides
+//! [`no_std::StaticRingBuffer`], a fixed-capacity variant that doesn't
+//! allocate.
+//! With the `reassemble` feature enabled, the [`reassemble`] module provides
+//! [`reassemble::Reassembler`], which feeds a [`Producer<u8>`] with
+//! out-of-order byte segments in stream order.
 //!
 //! A [`RingBuffer`] consists of two parts:
 //! a [`Producer`] for writing into the ring buffer and
@@ -29,20 +39,39 @@
 //! assert!(c.pop().is_err());
 //! ```
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(rust_2018_idioms)]
 #![deny(missing_docs)]
 
-use std::cell::Cell;
-use std::fmt;
-use std::marker::PhantomData;
-use std::mem;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+extern crate alloc;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::cell::Cell;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem;
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use cache_padded::CachePadded;
 
 mod error;
 
+#[cfg(feature = "async_")]
+pub mod async_;
+
+#[cfg(feature = "mio")]
+pub mod mio;
+
+#[cfg(feature = "std")]
+mod io;
+
+#[cfg(feature = "no_std")]
+pub mod no_std;
+
+#[cfg(feature = "reassemble")]
+pub mod reassemble;
+
 pub use error::{PeekError, PopError, PushError, SlicesError};
 
 /// A bounded single-producer single-consumer queue.
@@ -63,6 +92,18 @@ pub struct RingBuffer<T> {
     /// The queue capacity.
     capacity: usize,
 
+    /// The waker of a [`Producer`] that is currently waiting for free slots.
+    ///
+    /// Woken by the consumer side whenever slots are freed.
+    #[cfg(feature = "async_")]
+    producer_waker: atomic_waker::AtomicWaker,
+
+    /// The waker of a [`Consumer`] that is currently waiting for occupied slots.
+    ///
+    /// Woken by the producer side whenever new elements are pushed.
+    #[cfg(feature = "async_")]
+    consumer_waker: atomic_waker::AtomicWaker,
+
     /// Indicates that dropping a `Buffer<T>` may drop elements of type `T`.
     _marker: PhantomData<T>,
 }
@@ -107,6 +148,10 @@ impl<T> RingBuffer<T> {
             tail: CachePadded::new(AtomicUsize::new(0)),
             buffer,
             capacity,
+            #[cfg(feature = "async_")]
+            producer_waker: atomic_waker::AtomicWaker::new(),
+            #[cfg(feature = "async_")]
+            consumer_waker: atomic_waker::AtomicWaker::new(),
             _marker: PhantomData,
         }
     }
@@ -281,6 +326,8 @@ impl<T> Producer<T> {
             let tail = self.rb.increment1(tail);
             self.rb.tail.store(tail, Ordering::Release);
             self.tail.set(tail);
+            #[cfg(feature = "async_")]
+            self.rb.consumer_waker.wake();
             Ok(())
         } else {
             Err(PushError::Full(value))
@@ -416,13 +463,195 @@ where
 
         let first_len = n.min(self.rb.capacity - tail);
         Ok(PushSlices {
-            first: unsafe { std::slice::from_raw_parts_mut(self.rb.buffer.add(tail), first_len) },
-            second: unsafe { std::slice::from_raw_parts_mut(self.rb.buffer, n - first_len) },
+            first: unsafe { core::slice::from_raw_parts_mut(self.rb.buffer.add(tail), first_len) },
+            second: unsafe { core::slice::from_raw_parts_mut(self.rb.buffer, n - first_len) },
             producer: self,
         })
     }
 }
 
+impl<T> Producer<T>
+where
+    T: Copy,
+{
+    /// Copies as many elements of `src` into the queue as currently fit,
+    /// returning how many were written.
+    ///
+    /// Unlike [`Producer::push_slices()`], this works for any `T: Copy`
+    /// (no [`Default`] bound) and moves the data with at most two
+    /// `copy_nonoverlapping` calls (one per contiguous region), instead of
+    /// copying element by element through the returned slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(4).split();
+    ///
+    /// assert_eq!(p.write_slice(&[1, 2, 3]), 3);
+    /// assert_eq!(p.write_slice(&[4, 5]), 1);
+    /// assert_eq!(c.pop(), Ok(1));
+    /// ```
+    pub fn write_slice(&mut self, src: &[T]) -> usize {
+        let n = src.len().min(self.slots());
+        if n == 0 {
+            return 0;
+        }
+        let tail = self.rb.collapse_position(self.tail.get());
+        let first_len = n.min(self.rb.capacity - tail);
+        unsafe {
+            core::ptr::copy_nonoverlapping(src.as_ptr(), self.rb.buffer.add(tail), first_len);
+            core::ptr::copy_nonoverlapping(src[first_len..].as_ptr(), self.rb.buffer, n - first_len);
+        }
+        let tail = self.rb.increment(self.tail.get(), n);
+        self.rb.tail.store(tail, Ordering::Release);
+        self.tail.set(tail);
+        #[cfg(feature = "async_")]
+        self.rb.consumer_waker.wake();
+        n
+    }
+}
+
+impl<T> Producer<T> {
+    /// Returns a handle that postpones publishing the write position.
+    ///
+    /// Normally, every [`push()`](Producer::push) publishes the new tail
+    /// with a `Release` store immediately, which the [`Consumer`] picks up
+    /// on its next `Acquire` load. When pushing many elements one at a
+    /// time, this incurs one `Release` store (and the resulting cache-line
+    /// traffic) per element. [`PostponedProducer`] instead keeps advancing
+    /// a local position and only publishes it once, in
+    /// [`PostponedProducer::sync()`] or when the handle is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(3).split();
+    ///
+    /// {
+    ///     let mut p = p.postponed();
+    ///     assert!(p.push(1).is_ok());
+    ///     assert!(p.push(2).is_ok());
+    ///     // Nothing has been published yet:
+    ///     assert!(c.pop().is_err());
+    /// } // `p` is synced on drop.
+    ///
+    /// assert_eq!(c.pop(), Ok(1));
+    /// assert_eq!(c.pop(), Ok(2));
+    /// ```
+    pub fn postponed(&mut self) -> PostponedProducer<'_, T> {
+        let tail = self.tail.get();
+        PostponedProducer {
+            producer: self,
+            local_tail: Cell::new(tail),
+        }
+    }
+}
+
+/// A batched-commit handle for writing into the queue.
+///
+/// The shared tail position is only published (with a single `Release`
+/// store) on an explicit call to [`PostponedProducer::sync()`] or when this
+/// struct is dropped, instead of on every single push.
+///
+/// Created by [`Producer::postponed()`].
+#[derive(Debug)]
+pub struct PostponedProducer<'a, T> {
+    producer: &'a mut Producer<T>,
+
+    /// The not-yet-published tail position, always at least as far along
+    /// as `producer.tail`.
+    local_tail: Cell<usize>,
+}
+
+impl<T> PostponedProducer<'_, T> {
+    /// Attempts to push an element into the queue without publishing it yet.
+    ///
+    /// If the queue is full, the element is returned back as an error.
+    /// Capacity is checked against the not-yet-published local tail, so a
+    /// postponed producer can never write past the slots it has actually
+    /// reserved, even though the [`Consumer`] cannot yet see them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::{PushError, RingBuffer};
+    ///
+    /// let (mut p, _c) = RingBuffer::new(1).split();
+    /// let mut p = p.postponed();
+    ///
+    /// assert_eq!(p.push(1), Ok(()));
+    /// assert_eq!(p.push(2), Err(PushError::Full(2)));
+    /// ```
+    pub fn push(&mut self, value: T) -> Result<(), PushError<T>> {
+        if let Some(tail) = self.next_tail() {
+            unsafe {
+                self.producer.rb.slot_ptr(tail).write(value);
+            }
+            self.local_tail.set(self.producer.rb.increment1(tail));
+            Ok(())
+        } else {
+            Err(PushError::Full(value))
+        }
+    }
+
+    /// Returns the number of slots available for writing.
+    pub fn slots(&self) -> usize {
+        let head = self.producer.rb.head.load(Ordering::Acquire);
+        self.producer.head.set(head);
+        self.producer.rb.capacity - self.producer.rb.distance(head, self.local_tail.get())
+    }
+
+    /// Returns `true` if there are no slots available for writing.
+    pub fn is_full(&self) -> bool {
+        self.next_tail().is_none()
+    }
+
+    /// Returns the capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        self.producer.rb.capacity
+    }
+
+    /// Publishes all writes made so far, making them visible to the
+    /// [`Consumer`] with a single `Release` store.
+    pub fn sync(&mut self) {
+        let tail = self.local_tail.get();
+        self.producer.rb.tail.store(tail, Ordering::Release);
+        self.producer.tail.set(tail);
+        #[cfg(feature = "async_")]
+        self.producer.rb.consumer_waker.wake();
+    }
+
+    /// Get the tail position for writing the next slot, if available.
+    fn next_tail(&self) -> Option<usize> {
+        let tail = self.local_tail.get();
+
+        // Check if the queue is *possibly* full.
+        if self.producer.rb.distance(self.producer.head.get(), tail) == self.producer.rb.capacity
+        {
+            // Refresh the head ...
+            let head = self.producer.rb.head.load(Ordering::Acquire);
+            self.producer.head.set(head);
+
+            // ... and check if it's *really* full.
+            if self.producer.rb.distance(head, tail) == self.producer.rb.capacity {
+                return None;
+            }
+        }
+        Some(tail)
+    }
+}
+
+impl<T> Drop for PostponedProducer<'_, T> {
+    /// Publishes the final write position so no postponed progress is lost.
+    fn drop(&mut self) {
+        self.sync();
+    }
+}
+
 impl<T> fmt::Debug for Producer<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("Producer { .. }")
@@ -489,6 +718,8 @@ impl<T> Consumer<T> {
             let head = self.rb.increment1(head);
             self.rb.head.store(head, Ordering::Release);
             self.head.set(head);
+            #[cfg(feature = "async_")]
+            self.rb.producer_waker.wake();
             Ok(value)
         } else {
             Err(PopError::Empty)
@@ -691,6 +922,65 @@ impl<T> Consumer<T> {
         self.rb.capacity
     }
 
+    /// Drops up to `n` of the oldest readable elements, advancing the read
+    /// position, and returns how many were actually dropped (which may be
+    /// less than `n` if the queue holds fewer elements).
+    ///
+    /// Unlike popping in a loop, this publishes the new read position with
+    /// a single `Release` store.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(3).split();
+    ///
+    /// assert!(p.push(1).is_ok());
+    /// assert!(p.push(2).is_ok());
+    /// assert!(p.push(3).is_ok());
+    ///
+    /// assert_eq!(c.skip(2), 2);
+    /// assert_eq!(c.pop(), Ok(3));
+    /// ```
+    pub fn skip(&mut self, n: usize) -> usize {
+        let tail = self.rb.tail.load(Ordering::Acquire);
+        self.tail.set(tail);
+        let head = self.head.get();
+        let n = n.min(self.rb.distance(head, tail));
+        let mut pos = head;
+        for _ in 0..n {
+            unsafe {
+                self.rb.slot_ptr(pos).drop_in_place();
+            }
+            pos = self.rb.increment1(pos);
+        }
+        self.advance_head(head, n);
+        n
+    }
+
+    /// Drops all currently readable elements in one step, resynchronizing
+    /// the queue to empty.
+    ///
+    /// Handy for discarding stale queued data after an xrun or glitch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(2).split();
+    ///
+    /// assert!(p.push(1).is_ok());
+    /// assert!(p.push(2).is_ok());
+    ///
+    /// c.clear();
+    /// assert!(c.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.skip(usize::MAX);
+    }
+
     /// Get the head position for reading the next slot, if available.
     ///
     /// This is a strict subset of the functionality implemented in pop_slices()/peek_slices().
@@ -716,6 +1006,8 @@ impl<T> Consumer<T> {
         let head = self.rb.increment(head, n);
         self.rb.head.store(head, Ordering::Release);
         self.head.set(head);
+        #[cfg(feature = "async_")]
+        self.rb.producer_waker.wake();
     }
 
     /// Get slices holding `n` slots.
@@ -738,12 +1030,54 @@ impl<T> Consumer<T> {
         let head = self.rb.collapse_position(head);
         let first_len = n.min(self.rb.capacity - head);
         Ok((
-            unsafe { std::slice::from_raw_parts(self.rb.buffer.add(head), first_len) },
-            unsafe { std::slice::from_raw_parts(self.rb.buffer, n - first_len) },
+            unsafe { core::slice::from_raw_parts(self.rb.buffer.add(head), first_len) },
+            unsafe { core::slice::from_raw_parts(self.rb.buffer, n - first_len) },
         ))
     }
 }
 
+impl<T> Consumer<T>
+where
+    T: Copy,
+{
+    /// Copies as many elements from the queue into `dst` as are currently
+    /// available, returning how many were read.
+    ///
+    /// Unlike [`Consumer::pop_slices()`], this copies the elements out and
+    /// moves the data with at most two `copy_nonoverlapping` calls (one
+    /// per contiguous region), instead of copying element by element
+    /// through the returned slices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(4).split();
+    ///
+    /// assert_eq!(p.write_slice(&[1, 2, 3]), 3);
+    ///
+    /// let mut dst = [0; 4];
+    /// assert_eq!(c.read_slice(&mut dst), 3);
+    /// assert_eq!(dst, [1, 2, 3, 0]);
+    /// ```
+    pub fn read_slice(&mut self, dst: &mut [T]) -> usize {
+        let n = dst.len().min(self.slots());
+        if n == 0 {
+            return 0;
+        }
+        let head = self.head.get();
+        let head_pos = self.rb.collapse_position(head);
+        let first_len = n.min(self.rb.capacity - head_pos);
+        unsafe {
+            core::ptr::copy_nonoverlapping(self.rb.buffer.add(head_pos), dst.as_mut_ptr(), first_len);
+            core::ptr::copy_nonoverlapping(self.rb.buffer, dst[first_len..].as_mut_ptr(), n - first_len);
+        }
+        self.advance_head(head, n);
+        n
+    }
+}
+
 /// Contains two mutable slices from the ring buffer.
 /// When this structure is dropped (falls out of scope), the slots are made available for reading.
 ///
@@ -802,6 +1136,8 @@ impl<'a, T> Drop for PushSlices<'a, T> {
         );
         self.producer.rb.tail.store(tail, Ordering::Release);
         self.producer.tail.set(tail);
+        #[cfg(feature = "async_")]
+        self.producer.rb.consumer_waker.wake();
     }
 }
 
@@ -830,8 +1166,565 @@ impl<'a, T> Drop for PopSlices<'a, T> {
     }
 }
 
+impl<'a, T> IntoIterator for PeekSlices<'a, T> {
+    type Item = &'a T;
+    type IntoIter = core::iter::Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>;
+
+    /// Walks `first` then `second` as one logical sequence.
+    fn into_iter(self) -> Self::IntoIter {
+        self.first.iter().chain(self.second)
+    }
+}
+
+/// The iterator returned by `PopSlices::into_iter()`.
+///
+/// Keeps the [`PopSlices`] alive until the iterator itself is dropped, so
+/// the committed read position and element drops happen exactly as they
+/// would without iteration, even if iteration stops early.
+#[derive(Debug)]
+pub struct PopSlicesIter<'a, T> {
+    iter: core::iter::Chain<core::slice::Iter<'a, T>, core::slice::Iter<'a, T>>,
+    // Keeps the slots alive (and eventually dropped/committed) regardless
+    // of how far `iter` was actually driven.
+    _slices: PopSlices<'a, T>,
+}
+
+impl<'a, T> Iterator for PopSlicesIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for PopSlicesIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for PopSlicesIter<'_, T> {}
+
+impl<'a, T> IntoIterator for PopSlices<'a, T> {
+    type Item = &'a T;
+    type IntoIter = PopSlicesIter<'a, T>;
+
+    /// Walks `first` then `second` as one logical sequence.
+    ///
+    /// The elements are dropped and the read position is advanced once the
+    /// returned iterator is dropped, just as with [`PopSlices`] itself.
+    fn into_iter(self) -> Self::IntoIter {
+        let iter = self.first.iter().chain(self.second);
+        PopSlicesIter { iter, _slices: self }
+    }
+}
+
+/// The iterator returned by `PushSlices::into_iter()`.
+///
+/// Publishes the written slots (advancing the write position) when the
+/// iterator is dropped, even if iteration stops early, mirroring
+/// [`PushSlices`]'s own `Drop` impl.
+#[derive(Debug)]
+pub struct PushSlicesIter<'a, T> {
+    iter: core::iter::Chain<core::slice::IterMut<'a, T>, core::slice::IterMut<'a, T>>,
+    producer: &'a Producer<T>,
+    n: usize,
+}
+
+impl<'a, T> Iterator for PushSlicesIter<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for PushSlicesIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for PushSlicesIter<'_, T> {}
+
+impl<T> Drop for PushSlicesIter<'_, T> {
+    /// Makes the requested slots available for reading.
+    fn drop(&mut self) {
+        let tail = self.producer.rb.increment(self.producer.tail.get(), self.n);
+        self.producer.rb.tail.store(tail, Ordering::Release);
+        self.producer.tail.set(tail);
+        #[cfg(feature = "async_")]
+        self.producer.rb.consumer_waker.wake();
+    }
+}
+
+impl<'a, T> IntoIterator for PushSlices<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = PushSlicesIter<'a, T>;
+
+    /// Walks `first` then `second` as one logical sequence.
+    ///
+    /// The write position is published once the returned iterator is
+    /// dropped, just as with [`PushSlices`] itself.
+    fn into_iter(self) -> Self::IntoIter {
+        // `PushSlices` has a `Drop` impl, so its non-`Copy` fields can't be
+        // moved out normally; bypass it here since this constructor takes
+        // over publishing the write position (see `PushSlicesIter`'s own
+        // `Drop` impl above).
+        let this = mem::ManuallyDrop::new(self);
+        let first = unsafe { core::ptr::read(&this.first) };
+        let second = unsafe { core::ptr::read(&this.second) };
+        let n = first.len() + second.len();
+        PushSlicesIter {
+            iter: first.iter_mut().chain(second),
+            producer: this.producer,
+            n,
+        }
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Returns a handle that postpones publishing the read position.
+    ///
+    /// Mirrors [`Producer::postponed()`]: instead of a `Release` store to
+    /// `rb.head` on every single [`pop()`](Consumer::pop), the shared head
+    /// is only published once, in [`PostponedConsumer::sync()`] or when the
+    /// handle is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(2).split();
+    ///
+    /// assert!(p.push(1).is_ok());
+    /// assert!(p.push(2).is_ok());
+    ///
+    /// {
+    ///     let mut c = c.postponed();
+    ///     assert_eq!(c.pop(), Ok(1));
+    ///     // The freed slot has not been published yet:
+    ///     assert!(p.push(3).is_err());
+    /// } // `c` is synced on drop.
+    ///
+    /// assert!(p.push(3).is_ok());
+    /// ```
+    pub fn postponed(&mut self) -> PostponedConsumer<'_, T> {
+        let head = self.head.get();
+        PostponedConsumer {
+            consumer: self,
+            local_head: Cell::new(head),
+        }
+    }
+}
+
+/// A batched-commit handle for reading from the queue.
+///
+/// The shared head position is only published (with a single `Release`
+/// store) on an explicit call to [`PostponedConsumer::sync()`] or when this
+/// struct is dropped, instead of on every single pop.
+///
+/// Created by [`Consumer::postponed()`].
+#[derive(Debug)]
+pub struct PostponedConsumer<'a, T> {
+    consumer: &'a mut Consumer<T>,
+
+    /// The not-yet-published head position, always at least as far along
+    /// as `consumer.head`.
+    local_head: Cell<usize>,
+}
+
+impl<T> PostponedConsumer<'_, T> {
+    /// Attempts to pop an element from the queue without publishing the
+    /// freed slot yet.
+    ///
+    /// If the queue is empty, an error is returned. Availability is
+    /// re-checked against a freshly loaded tail the same way
+    /// [`Consumer::pop()`] does, so elements pushed by the other side in
+    /// the meantime are always picked up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::{PopError, RingBuffer};
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(2).split();
+    /// let mut c = c.postponed();
+    ///
+    /// assert_eq!(c.pop(), Err(PopError::Empty));
+    /// assert!(p.push(1).is_ok());
+    /// assert_eq!(c.pop(), Ok(1));
+    /// ```
+    pub fn pop(&mut self) -> Result<T, PopError> {
+        if let Some(head) = self.next_head() {
+            let value = unsafe { self.consumer.rb.slot_ptr(head).read() };
+            self.local_head.set(self.consumer.rb.increment1(head));
+            Ok(value)
+        } else {
+            Err(PopError::Empty)
+        }
+    }
+
+    /// Attempts to read an element from the queue without removing it.
+    ///
+    /// If the queue is empty, an error is returned.
+    pub fn peek(&self) -> Result<&T, PeekError> {
+        if let Some(head) = self.next_head() {
+            Ok(unsafe { &*self.consumer.rb.slot_ptr(head) })
+        } else {
+            Err(PeekError::Empty)
+        }
+    }
+
+    /// Returns the number of slots available for reading.
+    pub fn slots(&self) -> usize {
+        let tail = self.consumer.rb.tail.load(Ordering::Acquire);
+        self.consumer.tail.set(tail);
+        self.consumer.rb.distance(self.local_head.get(), tail)
+    }
+
+    /// Returns `true` if there are no slots available for reading.
+    pub fn is_empty(&self) -> bool {
+        self.next_head().is_none()
+    }
+
+    /// Returns the capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        self.consumer.rb.capacity
+    }
+
+    /// Publishes all pops made so far, making the freed slots available to
+    /// the [`Producer`] with a single `Release` store.
+    pub fn sync(&mut self) {
+        let head = self.local_head.get();
+        self.consumer.rb.head.store(head, Ordering::Release);
+        self.consumer.head.set(head);
+        #[cfg(feature = "async_")]
+        self.consumer.rb.producer_waker.wake();
+    }
+
+    /// Get the head position for reading the next slot, if available.
+    fn next_head(&self) -> Option<usize> {
+        let head = self.local_head.get();
+
+        // Check if the queue is *possibly* empty.
+        if head == self.consumer.tail.get() {
+            // Refresh the tail ...
+            let tail = self.consumer.rb.tail.load(Ordering::Acquire);
+            self.consumer.tail.set(tail);
+
+            // ... and check if it's *really* empty.
+            if head == tail {
+                return None;
+            }
+        }
+        Some(head)
+    }
+}
+
+impl<T> Drop for PostponedConsumer<'_, T> {
+    /// Publishes the final read position so no postponed progress is lost.
+    fn drop(&mut self) {
+        self.sync();
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Like [`Consumer::postponed()`], but takes ownership of the
+    /// [`Consumer`] instead of borrowing it.
+    ///
+    /// Useful when the postponed handle needs to outlive the scope that
+    /// would otherwise hold the borrow, e.g. when storing it in a struct.
+    pub fn into_postponed(self) -> IntoPostponedConsumer<T> {
+        let head = self.head.get();
+        IntoPostponedConsumer {
+            consumer: self,
+            local_head: Cell::new(head),
+        }
+    }
+}
+
+/// An owning variant of [`PostponedConsumer`].
+///
+/// Created by [`Consumer::into_postponed()`].
+#[derive(Debug)]
+pub struct IntoPostponedConsumer<T> {
+    consumer: Consumer<T>,
+
+    /// The not-yet-published head position, always at least as far along
+    /// as `consumer.head`.
+    local_head: Cell<usize>,
+}
+
+impl<T> IntoPostponedConsumer<T> {
+    /// See [`PostponedConsumer::pop()`].
+    pub fn pop(&mut self) -> Result<T, PopError> {
+        if let Some(head) = self.next_head() {
+            let value = unsafe { self.consumer.rb.slot_ptr(head).read() };
+            self.local_head.set(self.consumer.rb.increment1(head));
+            Ok(value)
+        } else {
+            Err(PopError::Empty)
+        }
+    }
+
+    /// See [`PostponedConsumer::peek()`].
+    pub fn peek(&self) -> Result<&T, PeekError> {
+        if let Some(head) = self.next_head() {
+            Ok(unsafe { &*self.consumer.rb.slot_ptr(head) })
+        } else {
+            Err(PeekError::Empty)
+        }
+    }
+
+    /// See [`PostponedConsumer::slots()`].
+    pub fn slots(&self) -> usize {
+        let tail = self.consumer.rb.tail.load(Ordering::Acquire);
+        self.consumer.tail.set(tail);
+        self.consumer.rb.distance(self.local_head.get(), tail)
+    }
+
+    /// See [`PostponedConsumer::is_empty()`].
+    pub fn is_empty(&self) -> bool {
+        self.next_head().is_none()
+    }
+
+    /// Returns the capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        self.consumer.rb.capacity
+    }
+
+    /// See [`PostponedConsumer::sync()`].
+    pub fn sync(&mut self) {
+        let head = self.local_head.get();
+        self.consumer.rb.head.store(head, Ordering::Release);
+        self.consumer.head.set(head);
+        #[cfg(feature = "async_")]
+        self.consumer.rb.producer_waker.wake();
+    }
+
+    /// Publishes any postponed progress and converts back into a plain
+    /// [`Consumer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, c) = RingBuffer::new(2).split();
+    /// let mut c = c.into_postponed();
+    ///
+    /// assert!(p.push(1).is_ok());
+    /// assert_eq!(c.pop(), Ok(1));
+    ///
+    /// let mut c = c.into_consumer();
+    /// assert_eq!(c.slots(), 0);
+    /// ```
+    pub fn into_consumer(self) -> Consumer<T> {
+        let mut this = mem::ManuallyDrop::new(self);
+        this.sync();
+        // Safety: `this` is never used again and its own `Drop` impl
+        // (which would otherwise call `sync()` a second time) is bypassed
+        // by `ManuallyDrop`.
+        unsafe { core::ptr::read(&this.consumer) }
+    }
+
+    fn next_head(&self) -> Option<usize> {
+        let head = self.local_head.get();
+
+        // Check if the queue is *possibly* empty.
+        if head == self.consumer.tail.get() {
+            // Refresh the tail ...
+            let tail = self.consumer.rb.tail.load(Ordering::Acquire);
+            self.consumer.tail.set(tail);
+
+            // ... and check if it's *really* empty.
+            if head == tail {
+                return None;
+            }
+        }
+        Some(head)
+    }
+}
+
+impl<T> Drop for IntoPostponedConsumer<T> {
+    /// Publishes the final read position so no postponed progress is lost.
+    fn drop(&mut self) {
+        self.sync();
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Removes exactly `n` elements and returns them through a draining iterator.
+    ///
+    /// If not enough slots are available for reading, an error is returned.
+    ///
+    /// Any elements not consumed by iterating are still removed (and
+    /// dropped) when the returned [`Drain`] goes out of scope, and the read
+    /// position is only published once, avoiding one `Release` store per
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::RingBuffer;
+    ///
+    /// let (mut p, mut c) = RingBuffer::new(3).split();
+    ///
+    /// assert!(p.push(10).is_ok());
+    /// assert!(p.push(20).is_ok());
+    /// assert!(p.push(30).is_ok());
+    ///
+    /// let v: Vec<_> = c.drain(3).unwrap().collect();
+    /// assert_eq!(v, [10, 20, 30]);
+    /// assert!(c.pop().is_err());
+    /// ```
+    pub fn drain(&mut self, n: usize) -> Result<Drain<'_, T>, SlicesError> {
+        let slots = self.slots();
+        if slots < n {
+            return Err(SlicesError::TooFewSlots(slots));
+        }
+        Ok(Drain {
+            consumer: self.postponed(),
+            remaining: n,
+        })
+    }
+}
+
+/// An iterator that removes and yields up to `n` elements from a [`Consumer`].
+///
+/// Created by [`Consumer::drain()`]. Any elements not consumed by iterating
+/// are dropped, and the read position is published, when this struct goes
+/// out of scope.
+#[derive(Debug)]
+pub struct Drain<'a, T> {
+    consumer: PostponedConsumer<'a, T>,
+    remaining: usize,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(
+            self.consumer
+                .pop()
+                .expect("n slots were reserved by Consumer::drain()"),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> Drop for Drain<'_, T> {
+    /// Drops any elements that weren't consumed by iterating.
+    fn drop(&mut self) {
+        while self.next().is_some() {}
+    }
+}
+
 impl<T> fmt::Debug for Consumer<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("Consumer { .. }")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postponed_producer_rechecks_capacity_against_local_tail() {
+        let (mut p, _c) = RingBuffer::new(2).split();
+        let mut p = p.postponed();
+
+        assert_eq!(p.push(1), Ok(()));
+        assert_eq!(p.push(2), Ok(()));
+        // Both slots are reserved locally, even though nothing has been
+        // published to the consumer yet.
+        assert_eq!(p.push(3), Err(PushError::Full(3)));
+    }
+
+    #[test]
+    fn postponed_consumer_rechecks_availability_against_fresh_tail() {
+        let (mut p, mut c) = RingBuffer::new(2).split();
+        let mut c = c.postponed();
+
+        assert_eq!(c.pop(), Err(PopError::Empty));
+        assert!(p.push(1).is_ok());
+        // The postponed consumer reloads the tail instead of trusting a
+        // stale "empty" result from before the push.
+        assert_eq!(c.pop(), Ok(1));
+        assert_eq!(c.pop(), Err(PopError::Empty));
+    }
+
+    #[test]
+    fn skip_drops_at_most_available_elements_and_advances_head() {
+        let (mut p, mut c) = RingBuffer::new(4).split();
+        assert!(p.push(1).is_ok());
+        assert!(p.push(2).is_ok());
+        assert!(p.push(3).is_ok());
+
+        assert_eq!(c.skip(2), 2);
+        assert_eq!(c.pop(), Ok(3));
+        assert_eq!(c.pop(), Err(PopError::Empty));
+
+        // Requesting more than what's available just drains everything.
+        assert!(p.push(4).is_ok());
+        assert_eq!(c.skip(10), 1);
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn clear_drains_queue_to_empty() {
+        let (mut p, mut c) = RingBuffer::new(2).split();
+        assert!(p.push(1).is_ok());
+        assert!(p.push(2).is_ok());
+
+        c.clear();
+        assert!(c.is_empty());
+        assert_eq!(p.push(3), Ok(()));
+        assert_eq!(c.pop(), Ok(3));
+    }
+
+    #[test]
+    fn write_slice_and_read_slice_handle_wraparound() {
+        let (mut p, mut c) = RingBuffer::new(4).split();
+
+        // Advance head/tail so the next write straddles the end of the
+        // buffer.
+        assert_eq!(p.write_slice(&[1, 2, 3]), 3);
+        let mut buf = [0; 2];
+        assert_eq!(c.read_slice(&mut buf), 2);
+        assert_eq!(buf, [1, 2]);
+
+        // This write wraps around the end of the backing storage.
+        assert_eq!(p.write_slice(&[4, 5, 6]), 3);
+        let mut buf = [0; 4];
+        assert_eq!(c.read_slice(&mut buf), 4);
+        assert_eq!(buf, [3, 4, 5, 6]);
+
+        // One more round trip, with both positions now in the second lap.
+        assert_eq!(p.write_slice(&[7, 8]), 2);
+        let mut buf = [0; 2];
+        assert_eq!(c.read_slice(&mut buf), 2);
+        assert_eq!(buf, [7, 8]);
+    }
+}
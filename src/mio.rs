@@ -0,0 +1,209 @@
+//! Readiness notification for `mio`/`epoll`-driven event loops.
+//!
+//! Wraps [`Producer`]/[`Consumer`] with a watermark-based
+//! [`mio::event::Source`](::mio::event::Source): [`MioConsumer`] becomes
+//! readable once occupied slots cross a low watermark, and [`MioProducer`]
+//! becomes writable once free slots cross a high watermark. Each side is
+//! backed by one end of an OS pipe, written to by its peer only on the
+//! relevant watermark-crossing transition, so the real-time side stays
+//! syscall-free except when it actually has to wake a blocked reactor
+//! thread. After a [`mio::Poll`](::mio::Poll) readiness event, call
+//! [`MioConsumer::clear_readiness()`]/[`MioProducer::clear_readiness()`]
+//! once to drain the underlying pipe; this is the reactor's job, not the
+//! hot path's, so [`push()`](MioProducer::push)/[`pop()`](MioConsumer::pop)
+//! never touch the pipe they read from.
+//!
+//! Enabled by the `mio` feature.
+
+use std::io::{self, Read, Write};
+
+use ::mio::event::Source;
+use ::mio::unix::pipe;
+use ::mio::{Interest, Registry, Token};
+
+use crate::{Consumer, PopError, Producer, PushError};
+
+/// Creates the two notification pipes and wraps `producer`/`consumer` with
+/// watermark-based `mio` readiness sources.
+///
+/// `low_watermark` is the number of occupied slots at which the consumer
+/// becomes readable; `high_watermark` is the number of free slots at which
+/// the producer becomes writable.
+pub fn with_readiness<T>(
+    producer: Producer<T>,
+    consumer: Consumer<T>,
+    low_watermark: usize,
+    high_watermark: usize,
+) -> io::Result<(MioProducer<T>, MioConsumer<T>)> {
+    // Signals "the queue became readable" (written to by the producer).
+    let (consumer_notify, consumer_readable) = pipe::new()?;
+    // Signals "the queue became writable" (written to by the consumer).
+    let (producer_notify, producer_writable) = pipe::new()?;
+
+    Ok((
+        MioProducer {
+            producer,
+            high_watermark,
+            low_watermark,
+            writable: producer_writable,
+            notify_consumer: consumer_notify,
+        },
+        MioConsumer {
+            consumer,
+            low_watermark,
+            high_watermark,
+            readable: consumer_readable,
+            notify_producer: producer_notify,
+        },
+    ))
+}
+
+/// Drains any buffered notification bytes so the readiness source doesn't
+/// keep firing for a transition that has already been handled.
+///
+/// Called once by the reactor after a readiness event, via
+/// [`MioConsumer::clear_readiness()`]/[`MioProducer::clear_readiness()`] —
+/// not by [`push()`](MioProducer::push)/[`pop()`](MioConsumer::pop), which
+/// would turn every element transfer into a syscall.
+fn drain(pipe: &mut pipe::Receiver) {
+    let mut buf = [0u8; 64];
+    loop {
+        match pipe.read(&mut buf) {
+            Ok(0) => break,
+            Ok(_) => continue,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(_) => break,
+        }
+    }
+}
+
+fn notify(pipe: &mut pipe::Sender) {
+    match pipe.write(&[0u8]) {
+        Ok(_) => {}
+        // The pipe already has an unconsumed notification buffered, or the
+        // reactor hasn't drained it yet; either way the peer is already
+        // going to wake up, so there's nothing more to do.
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+        Err(_) => {}
+    }
+}
+
+/// The producer side of a ring buffer, registrable with a [`mio::Poll`](::mio::Poll)
+/// as a source that becomes writable once free slots cross `high_watermark`.
+///
+/// Created by [`with_readiness()`].
+pub struct MioProducer<T> {
+    producer: Producer<T>,
+    high_watermark: usize,
+    /// The consumer's `low_watermark`, needed to notify it on the correct
+    /// occupied-slots transition.
+    low_watermark: usize,
+    writable: pipe::Receiver,
+    notify_consumer: pipe::Sender,
+}
+
+impl<T> MioProducer<T> {
+    /// Attempts to push an element, notifying the consumer's readiness
+    /// source if this push causes occupied slots to cross the low
+    /// watermark (which is the consumer's concern, not this side's).
+    pub fn push(&mut self, value: T) -> Result<(), PushError<T>> {
+        let occupied_before = self.producer.capacity() - self.producer.slots();
+        self.producer.push(value)?;
+        let occupied_after = occupied_before + 1;
+        if occupied_before < self.low_watermark && occupied_after >= self.low_watermark {
+            notify(&mut self.notify_consumer);
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if free slots are at or above `high_watermark`.
+    pub fn is_writable(&self) -> bool {
+        self.producer.slots() >= self.high_watermark
+    }
+
+    /// Drains the underlying readiness pipe.
+    ///
+    /// Call this once after waking up from a [`mio::Poll`](::mio::Poll)
+    /// event for this source, before relying on [`is_writable()`](Self::is_writable)
+    /// again; otherwise a notification byte written while this side was
+    /// already writable would make the source fire again for no new
+    /// transition.
+    pub fn clear_readiness(&mut self) {
+        drain(&mut self.writable);
+    }
+}
+
+impl<T> Source for MioProducer<T> {
+    fn register(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        self.writable.register(registry, token, interests)
+    }
+
+    fn reregister(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        self.writable.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.writable.deregister(registry)
+    }
+}
+
+/// The consumer side of a ring buffer, registrable with a [`mio::Poll`](::mio::Poll)
+/// as a source that becomes readable once occupied slots cross
+/// `low_watermark`.
+///
+/// Created by [`with_readiness()`].
+pub struct MioConsumer<T> {
+    consumer: Consumer<T>,
+    low_watermark: usize,
+    /// The producer's `high_watermark`, needed to notify it on the correct
+    /// free-slots transition.
+    high_watermark: usize,
+    readable: pipe::Receiver,
+    notify_producer: pipe::Sender,
+}
+
+impl<T> MioConsumer<T> {
+    /// Attempts to pop an element, notifying the producer's readiness
+    /// source if this pop causes free slots to cross the high watermark
+    /// (which is the producer's concern, not this side's).
+    pub fn pop(&mut self) -> Result<T, PopError> {
+        let free_before = self.consumer.capacity() - self.consumer.slots();
+        let value = self.consumer.pop()?;
+        let free_after = free_before + 1;
+        if free_before < self.high_watermark && free_after >= self.high_watermark {
+            notify(&mut self.notify_producer);
+        }
+        Ok(value)
+    }
+
+    /// Returns `true` if occupied slots are at or above `low_watermark`.
+    pub fn is_readable(&self) -> bool {
+        self.consumer.slots() >= self.low_watermark
+    }
+
+    /// Drains the underlying readiness pipe.
+    ///
+    /// Call this once after waking up from a [`mio::Poll`](::mio::Poll)
+    /// event for this source, before relying on [`is_readable()`](Self::is_readable)
+    /// again; otherwise a notification byte written while this side was
+    /// already readable would make the source fire again for no new
+    /// transition.
+    pub fn clear_readiness(&mut self) {
+        drain(&mut self.readable);
+    }
+}
+
+impl<T> Source for MioConsumer<T> {
+    fn register(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        self.readable.register(registry, token, interests)
+    }
+
+    fn reregister(&mut self, registry: &Registry, token: Token, interests: Interest) -> io::Result<()> {
+        self.readable.reregister(registry, token, interests)
+    }
+
+    fn deregister(&mut self, registry: &Registry) -> io::Result<()> {
+        self.readable.deregister(registry)
+    }
+}
@@ -0,0 +1,284 @@
+//! A fixed-capacity, non-allocating ring buffer for `no_std` targets.
+//!
+//! [`StaticRingBuffer`] mirrors [`RingBuffer`](crate::RingBuffer), but
+//! stores its slots inline in a const-generic array instead of a
+//! heap-allocated [`Vec`](std::vec::Vec), and its
+//! [`split()`](StaticRingBuffer::split) borrows the buffer instead of
+//! sharing it through an [`Arc`](std::sync::Arc). This trades away
+//! growability and ownership-sharing for zero allocation, following the
+//! approach taken by crates such as `heapless` and `starb`. The head/tail
+//! bookkeeping (wrapping, distances) is identical to [`RingBuffer`]'s.
+//!
+//! Enabled by the `no_std` feature.
+
+use core::cell::{Cell, UnsafeCell};
+use core::fmt;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use cache_padded::CachePadded;
+
+use crate::{PeekError, PopError, PushError};
+
+/// A bounded single-producer single-consumer queue with a fixed,
+/// compile-time capacity and no heap allocation.
+///
+/// Typically placed in a `static` or on the stack, then split into a
+/// [`StaticProducer`] and a [`StaticConsumer`] by [`StaticRingBuffer::split()`].
+pub struct StaticRingBuffer<T, const N: usize> {
+    head: CachePadded<AtomicUsize>,
+    tail: CachePadded<AtomicUsize>,
+    buffer: UnsafeCell<[MaybeUninit<T>; N]>,
+}
+
+// Safety: access to `buffer` is only ever performed through the
+// single producer or the single consumer handed out by `split()`,
+// following the same reasoning as the `Send` impls of `Producer`/`Consumer`.
+unsafe impl<T: Send, const N: usize> Sync for StaticRingBuffer<T, N> {}
+
+impl<T, const N: usize> StaticRingBuffer<T, N> {
+    /// Creates an empty [`StaticRingBuffer`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtrb::no_std::StaticRingBuffer;
+    ///
+    /// let mut rb = StaticRingBuffer::<f32, 100>::new();
+    /// let (mut p, mut c) = rb.split();
+    /// assert!(p.push(0.0f32).is_ok());
+    /// ```
+    pub fn new() -> Self {
+        assert!(N > 0, "capacity must be non-zero");
+        StaticRingBuffer {
+            head: CachePadded::new(AtomicUsize::new(0)),
+            tail: CachePadded::new(AtomicUsize::new(0)),
+            // Safety: an array of `MaybeUninit` never requires
+            // initialization, regardless of `T`.
+            buffer: UnsafeCell::new(unsafe { MaybeUninit::uninit().assume_init() }),
+        }
+    }
+
+    /// Splits the [`StaticRingBuffer`] into [`StaticProducer`] and [`StaticConsumer`].
+    pub fn split(&mut self) -> (StaticProducer<'_, T, N>, StaticConsumer<'_, T, N>) {
+        let p = StaticProducer {
+            rb: self,
+            head: Cell::new(0),
+            tail: Cell::new(0),
+        };
+        let c = StaticConsumer {
+            rb: self,
+            head: Cell::new(0),
+            tail: Cell::new(0),
+        };
+        (p, c)
+    }
+
+    /// Returns the capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    fn collapse_position(&self, pos: usize) -> usize {
+        if pos < N {
+            pos
+        } else {
+            pos - N
+        }
+    }
+
+    unsafe fn slot_ptr(&self, pos: usize) -> *mut T {
+        (*self.buffer.get())
+            .as_mut_ptr()
+            .add(self.collapse_position(pos))
+            .cast()
+    }
+
+    fn increment(&self, pos: usize, n: usize) -> usize {
+        let threshold = 2 * N - n;
+        if pos < threshold {
+            pos + n
+        } else {
+            pos - threshold
+        }
+    }
+
+    fn increment1(&self, pos: usize) -> usize {
+        if pos < 2 * N - 1 {
+            pos + 1
+        } else {
+            0
+        }
+    }
+
+    fn distance(&self, a: usize, b: usize) -> usize {
+        if a <= b {
+            b - a
+        } else {
+            2 * N - a + b
+        }
+    }
+}
+
+impl<T, const N: usize> Default for StaticRingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for StaticRingBuffer<T, N> {
+    /// Drops all non-empty slots.
+    fn drop(&mut self) {
+        let mut head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        while head != tail {
+            unsafe {
+                self.slot_ptr(head).drop_in_place();
+            }
+            head = self.increment(head, 1);
+        }
+    }
+}
+
+/// The producer side of a [`StaticRingBuffer`].
+///
+/// Created by [`StaticRingBuffer::split()`].
+pub struct StaticProducer<'a, T, const N: usize> {
+    rb: &'a StaticRingBuffer<T, N>,
+    head: Cell<usize>,
+    tail: Cell<usize>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for StaticProducer<'_, T, N> {}
+
+impl<T, const N: usize> StaticProducer<'_, T, N> {
+    /// Attempts to push an element into the queue.
+    ///
+    /// If the queue is full, the element is returned back as an error.
+    pub fn push(&mut self, value: T) -> Result<(), PushError<T>> {
+        if let Some(tail) = self.next_tail() {
+            unsafe {
+                self.rb.slot_ptr(tail).write(value);
+            }
+            let tail = self.rb.increment1(tail);
+            self.rb.tail.store(tail, Ordering::Release);
+            self.tail.set(tail);
+            Ok(())
+        } else {
+            Err(PushError::Full(value))
+        }
+    }
+
+    /// Returns the number of slots available for writing.
+    pub fn slots(&self) -> usize {
+        let head = self.rb.head.load(Ordering::Acquire);
+        self.head.set(head);
+        N - self.rb.distance(head, self.tail.get())
+    }
+
+    /// Returns `true` if there are no slots available for writing.
+    pub fn is_full(&self) -> bool {
+        self.next_tail().is_none()
+    }
+
+    /// Returns the capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    fn next_tail(&self) -> Option<usize> {
+        let tail = self.tail.get();
+        if self.rb.distance(self.head.get(), tail) == N {
+            let head = self.rb.head.load(Ordering::Acquire);
+            self.head.set(head);
+            if self.rb.distance(head, tail) == N {
+                return None;
+            }
+        }
+        Some(tail)
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for StaticProducer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("StaticProducer { .. }")
+    }
+}
+
+/// The consumer side of a [`StaticRingBuffer`].
+///
+/// Created by [`StaticRingBuffer::split()`].
+pub struct StaticConsumer<'a, T, const N: usize> {
+    rb: &'a StaticRingBuffer<T, N>,
+    head: Cell<usize>,
+    tail: Cell<usize>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for StaticConsumer<'_, T, N> {}
+
+impl<T, const N: usize> StaticConsumer<'_, T, N> {
+    /// Attempts to pop an element from the queue.
+    ///
+    /// If the queue is empty, an error is returned.
+    pub fn pop(&mut self) -> Result<T, PopError> {
+        if let Some(head) = self.next_head() {
+            let value = unsafe { self.rb.slot_ptr(head).read() };
+            let head = self.rb.increment1(head);
+            self.rb.head.store(head, Ordering::Release);
+            self.head.set(head);
+            Ok(value)
+        } else {
+            Err(PopError::Empty)
+        }
+    }
+
+    /// Attempts to read an element from the queue without removing it.
+    ///
+    /// If the queue is empty, an error is returned.
+    pub fn peek(&self) -> Result<&T, PeekError> {
+        if let Some(head) = self.next_head() {
+            Ok(unsafe { &*self.rb.slot_ptr(head) })
+        } else {
+            Err(PeekError::Empty)
+        }
+    }
+
+    /// Returns the number of slots available for reading.
+    pub fn slots(&self) -> usize {
+        let tail = self.rb.tail.load(Ordering::Acquire);
+        self.tail.set(tail);
+        self.rb.distance(self.head.get(), tail)
+    }
+
+    /// Returns `true` if there are no slots available for reading.
+    pub fn is_empty(&self) -> bool {
+        self.next_head().is_none()
+    }
+
+    /// Returns the capacity of the queue.
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    fn next_head(&self) -> Option<usize> {
+        let head = self.head.get();
+        if head == self.tail.get() {
+            let tail = self.rb.tail.load(Ordering::Acquire);
+            self.tail.set(tail);
+            if head == tail {
+                return None;
+            }
+        }
+        Some(head)
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for StaticConsumer<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("StaticConsumer { .. }")
+    }
+}